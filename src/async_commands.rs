@@ -1,8 +1,11 @@
+use crate::chunked::TsRangeChunked;
+use crate::commands;
 use crate::types::*;
 use redis::aio::ConnectionLike;
-use redis::{cmd, FromRedisValue, RedisFuture, ToRedisArgs};
+use redis::{FromRedisValue, RedisFuture, ToRedisArgs};
+use std::fmt::Display;
 
-/// Provides a high level synchronous API to work with redis time series data types. Uses some abstractions
+/// Provides a high level asynchronous API to work with redis time series data types. Uses some abstractions
 /// for easier handling of time series related redis command arguments. All commands are directly
 /// available on ConnectionLike types from the redis crate.
 /// ```rust,no_run
@@ -22,7 +25,7 @@ use redis::{cmd, FromRedisValue, RedisFuture, ToRedisArgs};
 pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
     /// Returns information about a redis time series key.
     fn ts_info<'a, K: ToRedisArgs + Send + Sync + 'a>(&'a mut self, key: K) -> RedisFuture<TsInfo> {
-        Box::pin(async move { cmd("TS.INFO").arg(key).query_async(self).await })
+        Box::pin(async move { commands::ts_info(key).query_async(self).await })
     }
 
     /// Creates a new redis time series key.
@@ -31,13 +34,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         key: K,
         options: TsOptions,
     ) -> RedisFuture<RV> {
-        Box::pin(async move {
-            cmd("TS.CREATE")
-                .arg(key)
-                .arg(options)
-                .query_async(self)
-                .await
-        })
+        Box::pin(async move { commands::ts_create(key, options).query_async(self).await })
     }
 
     /// Modifies an existing redis time series configuration.
@@ -46,13 +43,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         key: K,
         options: TsOptions,
     ) -> RedisFuture<RV> {
-        Box::pin(async move {
-            cmd("TS.ALTER")
-                .arg(key)
-                .arg(options.uncompressed(false))
-                .query_async(self)
-                .await
-        })
+        Box::pin(async move { commands::ts_alter(key, options).query_async(self).await })
     }
 
     /// Adds a single time series value with a timestamp to an existing redis time series.
@@ -68,14 +59,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         ts: TS,
         value: V,
     ) -> RedisFuture<RV> {
-        Box::pin(async move {
-            cmd("TS.ADD")
-                .arg(key)
-                .arg(ts)
-                .arg(value)
-                .query_async(self)
-                .await
-        })
+        Box::pin(async move { commands::ts_add(key, ts, value).query_async(self).await })
     }
 
     /// Adds a single time series value to an existing redis time series with redis system
@@ -90,14 +74,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         key: K,
         value: V,
     ) -> RedisFuture<RV> {
-        Box::pin(async move {
-            cmd("TS.ADD")
-                .arg(key)
-                .arg("*")
-                .arg(value)
-                .query_async(self)
-                .await
-        })
+        Box::pin(async move { commands::ts_add_now(key, value).query_async(self).await })
     }
 
     /// Adds a single time series value to a redis time series. If the time series does not
@@ -116,11 +93,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         options: TsOptions,
     ) -> RedisFuture<RV> {
         Box::pin(async move {
-            cmd("TS.ADD")
-                .arg(key)
-                .arg(ts)
-                .arg(value)
-                .arg(options)
+            commands::ts_add_create(key, ts, value, options)
                 .query_async(self)
                 .await
         })
@@ -137,7 +110,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         &'a mut self,
         values: &'a [(K, TS, V)],
     ) -> RedisFuture<RV> {
-        Box::pin(async move { cmd("TS.MADD").arg(values).query_async(self).await })
+        Box::pin(async move { commands::ts_madd(values).query_async(self).await })
     }
 
     /// Increments a time series value with redis system time.
@@ -151,7 +124,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         key: K,
         value: V,
     ) -> RedisFuture<RV> {
-        Box::pin(async move { cmd("TS.INCRBY").arg(key).arg(value).query_async(self).await })
+        Box::pin(async move { commands::ts_incrby_now(key, value).query_async(self).await })
     }
 
     /// Increments a time series value with given timestamp.
@@ -167,15 +140,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         ts: TS,
         value: V,
     ) -> RedisFuture<RV> {
-        Box::pin(async move {
-            cmd("TS.INCRBY")
-                .arg(key)
-                .arg(value)
-                .arg("TIMESTAMP")
-                .arg(ts)
-                .query_async(self)
-                .await
-        })
+        Box::pin(async move { commands::ts_incrby(key, ts, value).query_async(self).await })
     }
 
     /// Increments a time series value with timestamp. Time series will be created if it
@@ -194,12 +159,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         options: TsOptions,
     ) -> RedisFuture<RV> {
         Box::pin(async move {
-            cmd("TS.INCRBY")
-                .arg(key)
-                .arg(value)
-                .arg("TIMESTAMP")
-                .arg(ts)
-                .arg(options)
+            commands::ts_incrby_create(key, ts, value, options)
                 .query_async(self)
                 .await
         })
@@ -216,7 +176,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         key: K,
         value: V,
     ) -> RedisFuture<RV> {
-        Box::pin(async move { cmd("TS.DECRBY").arg(key).arg(value).query_async(self).await })
+        Box::pin(async move { commands::ts_decrby_now(key, value).query_async(self).await })
     }
 
     /// Decrements a time series value with given timestamp.
@@ -232,15 +192,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         ts: TS,
         value: V,
     ) -> RedisFuture<RV> {
-        Box::pin(async move {
-            cmd("TS.DECRBY")
-                .arg(key)
-                .arg(value)
-                .arg("TIMESTAMP")
-                .arg(ts)
-                .query_async(self)
-                .await
-        })
+        Box::pin(async move { commands::ts_decrby(key, ts, value).query_async(self).await })
     }
 
     /// Decrements a time series value with timestamp. Time series will be created if it
@@ -259,12 +211,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         options: TsOptions,
     ) -> RedisFuture<RV> {
         Box::pin(async move {
-            cmd("TS.DECRBY")
-                .arg(key)
-                .arg(value)
-                .arg("TIMESTAMP")
-                .arg(ts)
-                .arg(options)
+            commands::ts_decrby_create(key, ts, value, options)
                 .query_async(self)
                 .await
         })
@@ -278,10 +225,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         aggregation_type: TsAggregationType,
     ) -> RedisFuture<RV> {
         Box::pin(async move {
-            cmd("TS.CREATERULE")
-                .arg(source_key)
-                .arg(dest_key)
-                .arg(aggregation_type)
+            commands::ts_createrule(source_key, dest_key, aggregation_type)
                 .query_async(self)
                 .await
         })
@@ -293,13 +237,23 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         source_key: K,
         dest_key: K,
     ) -> RedisFuture<RV> {
-        Box::pin(async move {
-            cmd("TS.DELETERULE")
-                .arg(source_key)
-                .arg(dest_key)
-                .query_async(self)
-                .await
-        })
+        Box::pin(async move { commands::ts_deleterule(source_key, dest_key).query_async(self).await })
+    }
+
+    /// Deletes all samples between `from` and `to` (inclusive) from a redis
+    /// time series, returning the number of samples deleted.
+    fn ts_del<
+        'a,
+        K: ToRedisArgs + Send + Sync + 'a,
+        TS: ToRedisArgs + Send + Sync + 'a,
+        RV: FromRedisValue,
+    >(
+        &'a mut self,
+        key: K,
+        from: TS,
+        to: TS,
+    ) -> RedisFuture<RV> {
+        Box::pin(async move { commands::ts_del(key, from, to).query_async(self).await })
     }
 
     /// Returns the latest (current) value in a redis time series.
@@ -307,19 +261,15 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         &'a mut self,
         key: K,
     ) -> RedisFuture<Option<(TS, V)>> {
-        Box::pin(async move { cmd("TS.GET").arg(key).query_async(self).await.or(Ok(None)) })
+        Box::pin(async move { commands::ts_get(key).query_async(self).await.or(Ok(None)) })
     }
 
     /// Returns the latest (current) value from multiple redis time series.
-    fn ts_mget<
-        'a,
-        TS: Default + FromRedisValue + 'a,
-        V: Default + FromRedisValue + 'a,
-    >(
+    fn ts_mget<'a, TS: Default + FromRedisValue + 'a, V: Default + FromRedisValue + 'a>(
         &mut self,
         filter_options: TsFilterOptions,
     ) -> RedisFuture<TsMget<TS, V>> {
-        Box::pin(async move { cmd("TS.MGET").arg(filter_options).query_async(self).await })
+        Box::pin(async move { commands::ts_mget(filter_options).query_async(self).await })
     }
 
     #[doc(hidden)]
@@ -334,8 +284,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         key: K,
         query: TsRangeQuery,
     ) -> RedisFuture<TsRange<TS, V>> {
-        let mut c = cmd(command);
-        c.arg(key).arg(query);
+        let c = commands::ts_range(command, key, query);
         Box::pin(async move { c.query_async(self).await })
     }
 
@@ -367,29 +316,44 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
         self.range("TS.REVRANGE", key, query)
     }
 
-    #[doc(hidden)]
-    fn mrange<
+    /// Executes a redis time series range query, paging through the result
+    /// `chunk_size` samples at a time instead of materializing the whole
+    /// range in memory. See [`TsRangeChunked`].
+    fn ts_range_chunked<
         'a,
-        TS: Default + FromRedisValue + Copy,
-        V: Default + FromRedisValue + Copy,
+        K: ToRedisArgs + Clone + Send + Sync + 'a,
+        TS: Default + FromRedisValue + Copy + PartialEq + Display + Send + Sync + 'a,
+        V: Default + FromRedisValue + Copy + Send + Sync + 'a,
     >(
+        &'a mut self,
+        key: K,
+        query: TsRangeQuery,
+        chunk_size: u64,
+    ) -> TsRangeChunked<'a, Self, K, TS, V> {
+        TsRangeChunked::new(self, "TS.RANGE", key, query, chunk_size)
+    }
+
+    #[doc(hidden)]
+    fn mrange<'a, TS: Default + FromRedisValue + Copy, V: Default + FromRedisValue + Copy>(
         &mut self,
         command: &str,
         query: TsRangeQuery,
         filter_options: TsFilterOptions,
     ) -> RedisFuture<TsMrange<TS, V>> {
-        let mut c = cmd(command);
-        c.arg(query).arg(filter_options);
-
-        Box::pin(async move { c.query_async(self).await })
+        let grouped = filter_options.is_grouped();
+        let c = commands::ts_mrange(command, query, filter_options);
+        Box::pin(async move {
+            let result: TsMrange<TS, V> = c.query_async(self).await?;
+            Ok(if grouped {
+                result.populate_groups()
+            } else {
+                result
+            })
+        })
     }
 
     /// Executes multiple redis time series range queries.
-    fn ts_mrange<
-        'a,
-        TS: Default + FromRedisValue + Copy,
-        V: Default + FromRedisValue + Copy,
-    >(
+    fn ts_mrange<'a, TS: Default + FromRedisValue + Copy, V: Default + FromRedisValue + Copy>(
         &mut self,
         query: TsRangeQuery,
         filter_options: TsFilterOptions,
@@ -398,11 +362,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
     }
 
     /// Executes multiple redis time series revrange queries.
-    fn ts_mrevrange<
-        'a,
-        TS: Default + FromRedisValue + Copy,
-        V: Default + FromRedisValue + Copy,
-    >(
+    fn ts_mrevrange<'a, TS: Default + FromRedisValue + Copy, V: Default + FromRedisValue + Copy>(
         &mut self,
         query: TsRangeQuery,
         filter_options: TsFilterOptions,
@@ -412,12 +372,7 @@ pub trait AsyncTsCommands: ConnectionLike + Send + Sized {
 
     /// Returns a filtered list of redis time series keys.
     fn ts_queryindex(&mut self, filter_options: TsFilterOptions) -> RedisFuture<Vec<String>> {
-        Box::pin(async move {
-            cmd("TS.QUERYINDEX")
-                .arg(filter_options.get_filters())
-                .query_async(self)
-                .await
-        })
+        Box::pin(async move { commands::ts_queryindex(filter_options).query_async(self).await })
     }
 }
 
@@ -0,0 +1,302 @@
+use crate::commands;
+use crate::types::*;
+use futures_util::future::BoxFuture;
+use futures_util::stream::Stream;
+use redis::aio::ConnectionLike as AsyncConnectionLike;
+use redis::{ConnectionLike, FromRedisValue, RedisResult, ToRedisArgs};
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// How many trailing samples of a fetched page share its last timestamp.
+/// Re-issuing the next page `FROM` that timestamp (inclusive) would return
+/// them again, so the next page drops this many leading samples instead of
+/// trying to do arithmetic on a generic, possibly non-numeric, `TS`.
+fn trailing_duplicates<TS: PartialEq + Copy, V>(values: &[(TS, V)]) -> u64 {
+    match values.last() {
+        Some(&(last_ts, _)) => values
+            .iter()
+            .rev()
+            .take_while(|(ts, _)| *ts == last_ts)
+            .count() as u64,
+        None => 0,
+    }
+}
+
+/// Drops the samples already emitted for the current page's `from` boundary
+/// and works out the bookkeeping for the next page.
+///
+/// `skip` is the number of samples at `current_from` already emitted across
+/// *all* prior pages, not just the last one - the caller grows its next
+/// `COUNT` by the same amount (`chunk_size + skip`) so the server is asked
+/// to read far enough to reach fresh data even when a single timestamp has
+/// more duplicates than `chunk_size`. Without that cumulative count, a
+/// `from` that never advances (because every fetched sample is a duplicate
+/// we already saw) would just refetch the same page forever.
+fn next_page<TS: Copy + PartialEq + Display, V>(
+    values: Vec<(TS, V)>,
+    skip: u64,
+    current_from: Option<&str>,
+) -> (Vec<(TS, V)>, Option<String>, u64) {
+    let drop_n = (skip as usize).min(values.len());
+    let new_values: Vec<(TS, V)> = values.into_iter().skip(drop_n).collect();
+
+    match new_values.last() {
+        Some(&(last_ts, _)) => {
+            let last_ts = last_ts.to_string();
+            let dup = trailing_duplicates(&new_values);
+            let skip = if current_from == Some(last_ts.as_str()) {
+                skip + dup
+            } else {
+                dup
+            };
+            (new_values, Some(last_ts), skip)
+        }
+        None => (new_values, current_from.map(|s| s.to_string()), skip),
+    }
+}
+
+/// Pages through a `TS.RANGE` query in `chunk_size`-sized batches instead
+/// of materializing the whole range in memory. Returned by
+/// [`crate::AsyncTsCommands::ts_range_chunked`].
+pub struct TsRangeChunked<'a, C, K, TS, V> {
+    con: Option<&'a mut C>,
+    key: K,
+    command: &'static str,
+    query: TsRangeQuery,
+    chunk_size: u64,
+    from: Option<String>,
+    skip: u64,
+    done: bool,
+    page: std::vec::IntoIter<(TS, V)>,
+    #[allow(clippy::type_complexity)]
+    fetch: Option<BoxFuture<'a, (&'a mut C, RedisResult<TsRange<TS, V>>)>>,
+}
+
+impl<'a, C, K, TS, V> TsRangeChunked<'a, C, K, TS, V> {
+    pub(crate) fn new(
+        con: &'a mut C,
+        command: &'static str,
+        key: K,
+        query: TsRangeQuery,
+        chunk_size: u64,
+    ) -> Self {
+        TsRangeChunked {
+            con: Some(con),
+            key,
+            command,
+            query,
+            chunk_size,
+            from: None,
+            skip: 0,
+            done: false,
+            page: Vec::new().into_iter(),
+            fetch: None,
+        }
+    }
+}
+
+impl<'a, C, K, TS, V> Stream for TsRangeChunked<'a, C, K, TS, V>
+where
+    C: AsyncConnectionLike + Send,
+    K: ToRedisArgs + Clone + Send + Sync + 'a,
+    TS: Default + FromRedisValue + Copy + PartialEq + Display + Send + Sync + 'a,
+    V: Default + FromRedisValue + Copy + Send + Sync + 'a,
+{
+    type Item = RedisResult<(TS, V)>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(item) = self.page.next() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            if self.fetch.is_none() {
+                let con = self
+                    .con
+                    .take()
+                    .expect("TsRangeChunked polled after completion");
+                let total_fetch = self.chunk_size + self.skip;
+                let cmd = commands::ts_range(
+                    self.command,
+                    self.key.clone(),
+                    self.query.paged(self.from.as_deref(), total_fetch),
+                );
+                self.fetch = Some(Box::pin(async move {
+                    let result = cmd.query_async(con).await;
+                    (con, result)
+                }));
+            }
+
+            match self.fetch.as_mut().unwrap().as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready((con, result)) => {
+                    self.con = Some(con);
+                    self.fetch = None;
+
+                    match result {
+                        Err(e) => {
+                            self.done = true;
+                            return Poll::Ready(Some(Err(e)));
+                        }
+                        Ok(range) => {
+                            let total_fetch = self.chunk_size + self.skip;
+                            let raw_count = range.values.len() as u64;
+                            let (values, from, skip) =
+                                next_page(range.values, self.skip, self.from.as_deref());
+                            self.skip = skip;
+                            self.from = from;
+                            if raw_count < total_fetch {
+                                self.done = true;
+                            }
+                            self.page = values.into_iter();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Pages through a `TS.RANGE` query in `chunk_size`-sized batches instead
+/// of materializing the whole range in memory. Returned by
+/// [`crate::TsCommands::ts_range_chunked`].
+pub struct TsRangeChunkedIter<'a, C, K, TS, V> {
+    con: &'a mut C,
+    key: K,
+    command: &'static str,
+    query: TsRangeQuery,
+    chunk_size: u64,
+    from: Option<String>,
+    skip: u64,
+    done: bool,
+    page: std::vec::IntoIter<(TS, V)>,
+}
+
+impl<'a, C, K, TS, V> TsRangeChunkedIter<'a, C, K, TS, V> {
+    pub(crate) fn new(
+        con: &'a mut C,
+        command: &'static str,
+        key: K,
+        query: TsRangeQuery,
+        chunk_size: u64,
+    ) -> Self {
+        TsRangeChunkedIter {
+            con,
+            key,
+            command,
+            query,
+            chunk_size,
+            from: None,
+            skip: 0,
+            done: false,
+            page: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<'a, C, K, TS, V> Iterator for TsRangeChunkedIter<'a, C, K, TS, V>
+where
+    C: ConnectionLike,
+    K: ToRedisArgs + Clone,
+    TS: Default + FromRedisValue + Copy + PartialEq + Display,
+    V: Default + FromRedisValue + Copy,
+{
+    type Item = RedisResult<(TS, V)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.page.next() {
+                return Some(Ok(item));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let total_fetch = self.chunk_size + self.skip;
+            let query = self.query.paged(self.from.as_deref(), total_fetch);
+            let result: RedisResult<TsRange<TS, V>> =
+                commands::ts_range(self.command, self.key.clone(), query).query(self.con);
+
+            match result {
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                Ok(range) => {
+                    let raw_count = range.values.len() as u64;
+                    let (values, from, skip) =
+                        next_page(range.values, self.skip, self.from.as_deref());
+                    self.skip = skip;
+                    self.from = from;
+                    if raw_count < total_fetch {
+                        self.done = true;
+                    }
+                    self.page = values.into_iter();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mocks"))]
+mod tests {
+    use super::*;
+    use crate::mocks::MockTsConnection;
+    use crate::TsCommands;
+    use redis::Value;
+
+    /// Scripts a `TS.RANGE` mock serving `data` (sorted, ascending by
+    /// timestamp, duplicates allowed) by honouring the `from`/`COUNT`
+    /// arguments a real server would, so the chunked iterator's own paging
+    /// logic is what's under test rather than a canned response sequence.
+    fn ranged_mock(data: Vec<(u64, f64)>) -> MockTsConnection {
+        let mock = MockTsConnection::new();
+        mock.on_any(move |args| {
+            let from: u64 = std::str::from_utf8(&args[2])
+                .unwrap()
+                .parse()
+                .unwrap_or(0);
+            let count: usize = std::str::from_utf8(&args[5]).unwrap().parse().unwrap();
+            let page: Vec<Value> = data
+                .iter()
+                .filter(|(ts, _)| *ts >= from)
+                .take(count)
+                .map(|(ts, v)| Value::Bulk(vec![Value::Int(*ts as i64), Value::Data(v.to_string().into_bytes())]))
+                .collect();
+            Ok(Value::Bulk(page))
+        });
+        mock
+    }
+
+    #[test]
+    fn pages_through_distinct_timestamps_without_gaps_or_duplicates() {
+        let mock = ranged_mock(vec![(1, 1.0), (2, 2.0), (3, 3.0), (4, 4.0), (5, 5.0)]);
+        let mut con = mock.clone();
+
+        let values: RedisResult<Vec<(u64, f64)>> =
+            con.ts_range_chunked("my_ts", TsRangeQuery::new(0, "+"), 2).collect();
+
+        assert_eq!(
+            values.unwrap(),
+            vec![(1, 1.0), (2, 2.0), (3, 3.0), (4, 4.0), (5, 5.0)]
+        );
+    }
+
+    #[test]
+    fn terminates_and_does_not_re_emit_when_one_timestamp_has_more_samples_than_chunk_size() {
+        let mock = ranged_mock(vec![(5, 1.0), (5, 2.0), (5, 3.0), (6, 4.0)]);
+        let mut con = mock.clone();
+
+        let values: RedisResult<Vec<(u64, f64)>> =
+            con.ts_range_chunked("my_ts", TsRangeQuery::new(0, "+"), 2).collect();
+
+        assert_eq!(values.unwrap(), vec![(5, 1.0), (5, 2.0), (5, 3.0), (6, 4.0)]);
+    }
+}
@@ -0,0 +1,156 @@
+//! Shared command construction for [`crate::AsyncTsCommands`] and
+//! [`crate::TsCommands`]. Keeping the `TS.*` argument assembly in one place
+//! means the two traits can never drift apart on argument ordering - they
+//! only differ in whether the resulting [`Cmd`] is run with `query` or
+//! `query_async`.
+
+use crate::types::*;
+use redis::{cmd, Cmd, ToRedisArgs};
+
+pub(crate) fn ts_info<K: ToRedisArgs>(key: K) -> Cmd {
+    let mut c = cmd("TS.INFO");
+    c.arg(key);
+    c
+}
+
+pub(crate) fn ts_create<K: ToRedisArgs>(key: K, options: TsOptions) -> Cmd {
+    let mut c = cmd("TS.CREATE");
+    c.arg(key).arg(options);
+    c
+}
+
+pub(crate) fn ts_alter<K: ToRedisArgs>(key: K, options: TsOptions) -> Cmd {
+    let mut c = cmd("TS.ALTER");
+    c.arg(key).arg(options.uncompressed(false));
+    c
+}
+
+pub(crate) fn ts_add<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(key: K, ts: TS, value: V) -> Cmd {
+    let mut c = cmd("TS.ADD");
+    c.arg(key).arg(ts).arg(value);
+    c
+}
+
+pub(crate) fn ts_add_now<K: ToRedisArgs, V: ToRedisArgs>(key: K, value: V) -> Cmd {
+    let mut c = cmd("TS.ADD");
+    c.arg(key).arg("*").arg(value);
+    c
+}
+
+pub(crate) fn ts_add_create<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(
+    key: K,
+    ts: TS,
+    value: V,
+    options: TsOptions,
+) -> Cmd {
+    let mut c = cmd("TS.ADD");
+    c.arg(key).arg(ts).arg(value).arg(options);
+    c
+}
+
+pub(crate) fn ts_madd<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(values: &[(K, TS, V)]) -> Cmd {
+    let mut c = cmd("TS.MADD");
+    c.arg(values);
+    c
+}
+
+pub(crate) fn ts_incrby_now<K: ToRedisArgs, V: ToRedisArgs>(key: K, value: V) -> Cmd {
+    let mut c = cmd("TS.INCRBY");
+    c.arg(key).arg(value);
+    c
+}
+
+pub(crate) fn ts_incrby<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(key: K, ts: TS, value: V) -> Cmd {
+    let mut c = cmd("TS.INCRBY");
+    c.arg(key).arg(value).arg("TIMESTAMP").arg(ts);
+    c
+}
+
+pub(crate) fn ts_incrby_create<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(
+    key: K,
+    ts: TS,
+    value: V,
+    options: TsOptions,
+) -> Cmd {
+    let mut c = cmd("TS.INCRBY");
+    c.arg(key).arg(value).arg("TIMESTAMP").arg(ts).arg(options);
+    c
+}
+
+pub(crate) fn ts_decrby_now<K: ToRedisArgs, V: ToRedisArgs>(key: K, value: V) -> Cmd {
+    let mut c = cmd("TS.DECRBY");
+    c.arg(key).arg(value);
+    c
+}
+
+pub(crate) fn ts_decrby<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(key: K, ts: TS, value: V) -> Cmd {
+    let mut c = cmd("TS.DECRBY");
+    c.arg(key).arg(value).arg("TIMESTAMP").arg(ts);
+    c
+}
+
+pub(crate) fn ts_decrby_create<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(
+    key: K,
+    ts: TS,
+    value: V,
+    options: TsOptions,
+) -> Cmd {
+    let mut c = cmd("TS.DECRBY");
+    c.arg(key).arg(value).arg("TIMESTAMP").arg(ts).arg(options);
+    c
+}
+
+pub(crate) fn ts_createrule<K: ToRedisArgs>(
+    source_key: K,
+    dest_key: K,
+    aggregation_type: TsAggregationType,
+) -> Cmd {
+    let mut c = cmd("TS.CREATERULE");
+    c.arg(source_key).arg(dest_key).arg(aggregation_type);
+    c
+}
+
+pub(crate) fn ts_deleterule<K: ToRedisArgs>(source_key: K, dest_key: K) -> Cmd {
+    let mut c = cmd("TS.DELETERULE");
+    c.arg(source_key).arg(dest_key);
+    c
+}
+
+pub(crate) fn ts_del<K: ToRedisArgs, TS: ToRedisArgs>(key: K, from: TS, to: TS) -> Cmd {
+    let mut c = cmd("TS.DEL");
+    c.arg(key).arg(from).arg(to);
+    c
+}
+
+pub(crate) fn ts_get<K: ToRedisArgs>(key: K) -> Cmd {
+    let mut c = cmd("TS.GET");
+    c.arg(key);
+    c
+}
+
+pub(crate) fn ts_mget(filter_options: TsFilterOptions) -> Cmd {
+    let mut c = cmd("TS.MGET");
+    c.arg(filter_options.without_group_by());
+    c
+}
+
+pub(crate) fn ts_range<K: ToRedisArgs>(command: &str, key: K, query: TsRangeQuery) -> Cmd {
+    let mut c = cmd(command);
+    c.arg(key).arg(query);
+    c
+}
+
+pub(crate) fn ts_mrange(command: &str, query: TsRangeQuery, filter_options: TsFilterOptions) -> Cmd {
+    let mut c = cmd(command);
+    query.write_range_args(&mut c);
+    filter_options.write_label_selection_args(&mut c);
+    query.write_aggregation_args(&mut c);
+    filter_options.write_filter_args(&mut c);
+    c
+}
+
+pub(crate) fn ts_queryindex(filter_options: TsFilterOptions) -> Cmd {
+    let mut c = cmd("TS.QUERYINDEX");
+    c.arg(filter_options.get_filters());
+    c
+}
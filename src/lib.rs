@@ -0,0 +1,25 @@
+//! `redis_ts` adds convenient high level commands for
+//! [`RedisTimeSeries`](https://oss.redis.com/redistimeseries/) to the
+//! [`redis`](https://docs.rs/redis) crate.
+//!
+//! Both an async (`AsyncTsCommands`) and a sync (`TsCommands`) flavour of the
+//! API are provided, so the same ergonomic wrappers work whether you are
+//! holding a `redis::aio::Connection` or a plain, blocking `redis::Connection`.
+
+mod commands;
+mod types;
+
+mod async_commands;
+mod chunked;
+#[cfg(feature = "mocks")]
+mod mocks;
+mod pipeline;
+mod sync_commands;
+
+pub use crate::async_commands::AsyncTsCommands;
+pub use crate::chunked::{TsRangeChunked, TsRangeChunkedIter};
+#[cfg(feature = "mocks")]
+pub use crate::mocks::{MockHandler, MockTsConnection};
+pub use crate::pipeline::TsPipeline;
+pub use crate::sync_commands::TsCommands;
+pub use crate::types::*;
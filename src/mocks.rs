@@ -0,0 +1,352 @@
+//! A mock [`ConnectionLike`] (both the blocking and the async flavour) for
+//! asserting on the exact commands `redis_ts` emits without a live
+//! RedisTimeSeries instance. Enabled by the `mocks` feature.
+//!
+//! Since [`crate::AsyncTsCommands`]/[`crate::TsCommands`] are blanket
+//! implemented over any `ConnectionLike`, a [`MockTsConnection`] works with
+//! the whole API immediately:
+//!
+//! ```rust,no_run
+//! use redis::Value;
+//! use redis_ts::{MockTsConnection, TsCommands, TsOptions};
+//!
+//! let mock = MockTsConnection::new();
+//! mock.on("TS.ADD", Value::Int(1));
+//!
+//! let mut con = mock.clone();
+//! let _: i64 = con.ts_add_create("my_ts", 1, 2.0, TsOptions::default()).unwrap();
+//!
+//! assert_eq!(con.recorded()[0][0], b"TS.ADD");
+//! ```
+
+use redis::aio::ConnectionLike as AsyncConnectionLike;
+use redis::{Cmd, ConnectionLike, ErrorKind, Pipeline, RedisError, RedisFuture, RedisResult, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A closure computing a scripted response from a command's raw argument
+/// list (e.g. `[b"TS.ADD", b"my_ts", b"1", b"2"]`).
+pub type MockHandler = Arc<dyn Fn(&[Vec<u8>]) -> RedisResult<Value> + Send + Sync>;
+
+#[derive(Default)]
+struct MockState {
+    recorded: Vec<Vec<Vec<u8>>>,
+    responses: HashMap<String, Value>,
+    handler: Option<MockHandler>,
+}
+
+/// A fake redis connection that records every command it is handed and
+/// answers with a scripted [`redis::Value`], keyed by command name or a
+/// user-supplied closure.
+///
+/// Cloning a `MockTsConnection` shares the same recorded history and
+/// scripted responses - clone it to hand out a connection while keeping a
+/// handle to inspect what it received.
+#[derive(Clone, Default)]
+pub struct MockTsConnection {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockTsConnection {
+    /// Creates a connection with no scripted responses.
+    pub fn new() -> Self {
+        MockTsConnection::default()
+    }
+
+    /// Scripts a fixed response for every command whose name (e.g.
+    /// `"TS.ADD"`) matches, overriding any previous response for that name.
+    pub fn on(&self, command: &str, value: Value) -> &Self {
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .insert(command.to_string(), value);
+        self
+    }
+
+    /// Scripts a response computed from the full argument list of every
+    /// command this connection receives, taking priority over `on`.
+    pub fn on_any<F>(&self, handler: F) -> &Self
+    where
+        F: Fn(&[Vec<u8>]) -> RedisResult<Value> + Send + Sync + 'static,
+    {
+        self.state.lock().unwrap().handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Returns every command received so far, oldest first, as its raw
+    /// argument list.
+    pub fn recorded(&self) -> Vec<Vec<Vec<u8>>> {
+        self.state.lock().unwrap().recorded.clone()
+    }
+
+    fn handle(&self, args: Vec<Vec<u8>>) -> RedisResult<Value> {
+        let mut state = self.state.lock().unwrap();
+
+        let response = if let Some(handler) = state.handler.clone() {
+            handler(&args)
+        } else {
+            let name = args
+                .first()
+                .map(|n| String::from_utf8_lossy(n).to_string())
+                .unwrap_or_default();
+            state
+                .responses
+                .get(&name)
+                .cloned()
+                .ok_or_else(|| unscripted_command(&name))
+        };
+
+        state.recorded.push(args);
+        response
+    }
+}
+
+fn unscripted_command(name: &str) -> RedisError {
+    RedisError::from((
+        ErrorKind::TypeError,
+        "MockTsConnection received a command with no scripted response",
+        name.to_string(),
+    ))
+}
+
+/// Splits the bytes of one or more packed RESP commands (as produced by
+/// `Cmd::get_packed_command`/`Pipeline::get_packed_pipeline`) back into
+/// their argument lists.
+fn unpack_commands(bytes: &[u8]) -> Vec<Vec<Vec<u8>>> {
+    let mut commands = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'*' {
+            break;
+        }
+        let header_end = match find_crlf(bytes, i) {
+            Some(end) => end,
+            None => break,
+        };
+        let arity: usize = std::str::from_utf8(&bytes[i + 1..header_end])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        i = header_end + 2;
+
+        let mut args = Vec::with_capacity(arity);
+        for _ in 0..arity {
+            if i >= bytes.len() || bytes[i] != b'$' {
+                break;
+            }
+            let len_end = match find_crlf(bytes, i) {
+                Some(end) => end,
+                None => break,
+            };
+            let len: usize = std::str::from_utf8(&bytes[i + 1..len_end])
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0);
+            let data_start = len_end + 2;
+            let data_end = data_start + len;
+            if data_end > bytes.len() {
+                break;
+            }
+            args.push(bytes[data_start..data_end].to_vec());
+            i = data_end + 2;
+        }
+        commands.push(args);
+    }
+
+    commands
+}
+
+fn find_crlf(bytes: &[u8], from: usize) -> Option<usize> {
+    bytes[from..]
+        .windows(2)
+        .position(|w| w == b"\r\n")
+        .map(|p| p + from)
+}
+
+impl ConnectionLike for MockTsConnection {
+    fn req_packed_command(&mut self, cmd: &[u8]) -> RedisResult<Value> {
+        let args = unpack_commands(cmd).pop().unwrap_or_default();
+        self.handle(args)
+    }
+
+    fn req_packed_commands(
+        &mut self,
+        cmd: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> RedisResult<Vec<Value>> {
+        unpack_commands(cmd)
+            .into_iter()
+            .skip(offset)
+            .take(count)
+            .map(|args| self.handle(args))
+            .collect()
+    }
+
+    fn get_db(&self) -> i64 {
+        0
+    }
+
+    fn check_connection(&mut self) -> bool {
+        true
+    }
+
+    fn is_open(&self) -> bool {
+        true
+    }
+}
+
+impl AsyncConnectionLike for MockTsConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        let args = unpack_commands(&cmd.get_packed_command())
+            .pop()
+            .unwrap_or_default();
+        let result = self.handle(args);
+        Box::pin(async move { result })
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        let result = unpack_commands(&cmd.get_packed_pipeline())
+            .into_iter()
+            .skip(offset)
+            .take(count)
+            .map(|args| self.handle(args))
+            .collect();
+        Box::pin(async move { result })
+    }
+
+    fn get_db(&self) -> i64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        TsAggregationType, TsAlign, TsBucketTimestamp, TsCommands, TsFilterOptions, TsGroupBy,
+        TsMrange, TsOptions, TsRange, TsRangeQuery, TsReducer,
+    };
+
+    #[test]
+    fn ts_add_create_serializes_ts_add_key_ts_value_options_in_order() {
+        let mock = MockTsConnection::new();
+        mock.on("TS.ADD", Value::Okay);
+        let mut con = mock.clone();
+
+        let options = TsOptions::default()
+            .retention_time(60000)
+            .label("sensor", "1");
+        let _: () = con.ts_add_create("my_ts", 123, 4.5, options).unwrap();
+
+        assert_eq!(
+            mock.recorded(),
+            vec![vec![
+                b"TS.ADD".to_vec(),
+                b"my_ts".to_vec(),
+                b"123".to_vec(),
+                b"4.5".to_vec(),
+                b"RETENTION".to_vec(),
+                b"60000".to_vec(),
+                b"LABELS".to_vec(),
+                b"sensor".to_vec(),
+                b"1".to_vec(),
+            ]]
+        );
+    }
+
+    #[test]
+    fn ts_range_emits_filters_and_aggregation_in_grammar_order() {
+        let mock = MockTsConnection::new();
+        mock.on("TS.RANGE", Value::Bulk(vec![]));
+        let mut con = mock.clone();
+
+        let query = TsRangeQuery::new(0, "+")
+            .filter_by_ts(vec![1, 2, 3])
+            .filter_by_value(0.0, 100.0)
+            .count(10)
+            .align(TsAlign::Start)
+            .aggregation_type(TsAggregationType::Avg(60000))
+            .bucket_timestamp(TsBucketTimestamp::Start)
+            .empty();
+
+        let _: TsRange<u64, f64> = con.ts_range("my_ts", query).unwrap();
+
+        assert_eq!(
+            mock.recorded(),
+            vec![vec![
+                b"TS.RANGE".to_vec(),
+                b"my_ts".to_vec(),
+                b"0".to_vec(),
+                b"+".to_vec(),
+                b"FILTER_BY_TS".to_vec(),
+                b"1".to_vec(),
+                b"2".to_vec(),
+                b"3".to_vec(),
+                b"FILTER_BY_VALUE".to_vec(),
+                b"0".to_vec(),
+                b"100".to_vec(),
+                b"COUNT".to_vec(),
+                b"10".to_vec(),
+                b"ALIGN".to_vec(),
+                b"-".to_vec(),
+                b"AGGREGATION".to_vec(),
+                b"avg".to_vec(),
+                b"60000".to_vec(),
+                b"BUCKETTIMESTAMP".to_vec(),
+                b"start".to_vec(),
+                b"EMPTY".to_vec(),
+            ]]
+        );
+    }
+
+    #[test]
+    fn ts_mrange_emits_label_selection_before_count_and_aggregation() {
+        let mock = MockTsConnection::new();
+        mock.on("TS.MRANGE", Value::Bulk(vec![]));
+        let mut con = mock.clone();
+
+        let query = TsRangeQuery::new(0, "+").count(10).aggregation_type(TsAggregationType::Avg(60000));
+        let filter_options = TsFilterOptions::new(vec!["sensor=1".to_string()])
+            .selected_labels(vec!["sensor".to_string()])
+            .group_by(TsGroupBy::new("sensor", TsReducer::Avg));
+
+        let _: TsMrange<u64, f64> = con.ts_mrange(query, filter_options).unwrap();
+
+        assert_eq!(
+            mock.recorded(),
+            vec![vec![
+                b"TS.MRANGE".to_vec(),
+                b"0".to_vec(),
+                b"+".to_vec(),
+                b"SELECTED_LABELS".to_vec(),
+                b"sensor".to_vec(),
+                b"COUNT".to_vec(),
+                b"10".to_vec(),
+                b"AGGREGATION".to_vec(),
+                b"avg".to_vec(),
+                b"60000".to_vec(),
+                b"FILTER".to_vec(),
+                b"sensor=1".to_vec(),
+                b"GROUPBY".to_vec(),
+                b"sensor".to_vec(),
+                b"REDUCE".to_vec(),
+                b"avg".to_vec(),
+            ]]
+        );
+    }
+
+    #[test]
+    fn unscripted_command_is_an_error_not_a_panic() {
+        let mut con = MockTsConnection::new();
+        let result: RedisResult<()> = con.ts_create("my_ts", TsOptions::default());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::commands;
+use crate::types::*;
+use redis::aio::ConnectionLike as AsyncConnectionLike;
+use redis::{ConnectionLike, FromRedisValue, Pipeline, RedisResult, ToRedisArgs};
+
+/// A typed wrapper around [`redis::Pipeline`] for batching multiple `TS.*`
+/// commands into a single round trip. Every method here mirrors its
+/// counterpart on [`crate::AsyncTsCommands`]/[`crate::TsCommands`], reuses
+/// the same command construction from [`crate::commands`], and is
+/// chainable so a batch of samples can be built up in one expression:
+///
+/// ```rust,no_run
+/// # async fn run() -> redis::RedisResult<()> {
+/// use redis_ts::TsPipeline;
+///
+/// let client = redis::Client::open("redis://127.0.0.1/")?;
+/// let mut con = client.get_async_connection().await?;
+///
+/// let _: () = TsPipeline::new()
+///     .ts_add("my_ts", 1, 1.0)
+///     .ts_add("my_ts", 2, 2.0)
+///     .query_async(&mut con)
+///     .await?;
+/// # Ok(()) }
+/// ```
+///
+/// As with `redis::Pipeline`, the type requested from `query`/`query_async`
+/// must match the shape of the queued commands, e.g. a tuple of one element
+/// per command or a `Vec<redis::Value>`.
+#[derive(Default, Clone)]
+pub struct TsPipeline {
+    pipe: Pipeline,
+}
+
+impl TsPipeline {
+    /// Creates an empty pipeline.
+    pub fn new() -> Self {
+        TsPipeline {
+            pipe: Pipeline::new(),
+        }
+    }
+
+    /// Makes the pipeline atomic, i.e. wraps it in a `MULTI`/`EXEC` block.
+    pub fn atomic(&mut self) -> &mut Self {
+        self.pipe.atomic();
+        self
+    }
+
+    /// Queues `TS.CREATE`.
+    pub fn ts_create<K: ToRedisArgs>(&mut self, key: K, options: TsOptions) -> &mut Self {
+        self.pipe.add_command(commands::ts_create(key, options));
+        self
+    }
+
+    /// Queues `TS.ALTER`.
+    pub fn ts_alter<K: ToRedisArgs>(&mut self, key: K, options: TsOptions) -> &mut Self {
+        self.pipe.add_command(commands::ts_alter(key, options));
+        self
+    }
+
+    /// Queues `TS.ADD`.
+    pub fn ts_add<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+    ) -> &mut Self {
+        self.pipe.add_command(commands::ts_add(key, ts, value));
+        self
+    }
+
+    /// Queues `TS.ADD` with `*` as the timestamp.
+    pub fn ts_add_now<K: ToRedisArgs, V: ToRedisArgs>(&mut self, key: K, value: V) -> &mut Self {
+        self.pipe.add_command(commands::ts_add_now(key, value));
+        self
+    }
+
+    /// Queues `TS.ADD` with creation options attached.
+    pub fn ts_add_create<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+        options: TsOptions,
+    ) -> &mut Self {
+        self.pipe
+            .add_command(commands::ts_add_create(key, ts, value, options));
+        self
+    }
+
+    /// Queues `TS.MADD`.
+    pub fn ts_madd<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        values: &[(K, TS, V)],
+    ) -> &mut Self {
+        self.pipe.add_command(commands::ts_madd(values));
+        self
+    }
+
+    /// Queues `TS.INCRBY` using redis system time.
+    pub fn ts_incrby_now<K: ToRedisArgs, V: ToRedisArgs>(&mut self, key: K, value: V) -> &mut Self {
+        self.pipe.add_command(commands::ts_incrby_now(key, value));
+        self
+    }
+
+    /// Queues `TS.INCRBY` with an explicit timestamp.
+    pub fn ts_incrby<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+    ) -> &mut Self {
+        self.pipe.add_command(commands::ts_incrby(key, ts, value));
+        self
+    }
+
+    /// Queues `TS.DECRBY` using redis system time.
+    pub fn ts_decrby_now<K: ToRedisArgs, V: ToRedisArgs>(&mut self, key: K, value: V) -> &mut Self {
+        self.pipe.add_command(commands::ts_decrby_now(key, value));
+        self
+    }
+
+    /// Queues `TS.DECRBY` with an explicit timestamp.
+    pub fn ts_decrby<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+    ) -> &mut Self {
+        self.pipe.add_command(commands::ts_decrby(key, ts, value));
+        self
+    }
+
+    /// Queues `TS.CREATERULE`.
+    pub fn ts_createrule<K: ToRedisArgs>(
+        &mut self,
+        source_key: K,
+        dest_key: K,
+        aggregation_type: TsAggregationType,
+    ) -> &mut Self {
+        self.pipe
+            .add_command(commands::ts_createrule(source_key, dest_key, aggregation_type));
+        self
+    }
+
+    /// Queues `TS.DELETERULE`.
+    pub fn ts_deleterule<K: ToRedisArgs>(&mut self, source_key: K, dest_key: K) -> &mut Self {
+        self.pipe
+            .add_command(commands::ts_deleterule(source_key, dest_key));
+        self
+    }
+
+    /// Executes the pipeline over a blocking connection.
+    pub fn query<T: FromRedisValue>(&self, con: &mut dyn ConnectionLike) -> RedisResult<T> {
+        self.pipe.query(con)
+    }
+
+    /// Executes the pipeline over an async connection.
+    pub async fn query_async<T: FromRedisValue>(
+        &self,
+        con: &mut (impl AsyncConnectionLike + Send),
+    ) -> RedisResult<T> {
+        self.pipe.query_async(con).await
+    }
+}
+
+#[cfg(all(test, feature = "mocks"))]
+mod tests {
+    use super::*;
+    use crate::mocks::MockTsConnection;
+    use redis::Value;
+
+    #[test]
+    fn batches_queued_commands_in_order() {
+        let mock = MockTsConnection::new();
+        mock.on("TS.ADD", Value::Okay);
+        let mut con = mock.clone();
+
+        let _: (Value, Value) = TsPipeline::new()
+            .ts_add("my_ts", 1, 1.0)
+            .ts_add("my_ts", 2, 2.0)
+            .query(&mut con)
+            .unwrap();
+
+        assert_eq!(
+            mock.recorded(),
+            vec![
+                vec![b"TS.ADD".to_vec(), b"my_ts".to_vec(), b"1".to_vec(), b"1".to_vec()],
+                vec![b"TS.ADD".to_vec(), b"my_ts".to_vec(), b"2".to_vec(), b"2".to_vec()],
+            ]
+        );
+    }
+}
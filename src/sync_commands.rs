@@ -0,0 +1,276 @@
+use crate::chunked::TsRangeChunkedIter;
+use crate::commands;
+use crate::types::*;
+use redis::{ConnectionLike, FromRedisValue, RedisResult, ToRedisArgs};
+use std::fmt::Display;
+
+/// Provides a high level synchronous API to work with redis time series data types. Uses some abstractions
+/// for easier handling of time series related redis command arguments. All commands are directly
+/// available on ConnectionLike types from the redis crate.
+/// ```rust,no_run
+/// # fn run() -> redis::RedisResult<()> {
+/// use redis_ts::{TsCommands, TsOptions};
+///
+/// let client = redis::Client::open("redis://127.0.0.1/")?;
+/// let mut con = client.get_connection()?;
+///
+/// let _:() = con.ts_create("my_ts", TsOptions::default())?;
+/// let ts:u64 = con.ts_add_now("my_ts", 2.0)?;
+/// let v:Option<(u64,f64)> = con.ts_get("my_ts")?;
+/// # Ok(()) }
+/// ```
+///
+pub trait TsCommands: ConnectionLike + Sized {
+    /// Returns information about a redis time series key.
+    fn ts_info<K: ToRedisArgs>(&mut self, key: K) -> RedisResult<TsInfo> {
+        commands::ts_info(key).query(self)
+    }
+
+    /// Creates a new redis time series key.
+    fn ts_create<K: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        options: TsOptions,
+    ) -> RedisResult<RV> {
+        commands::ts_create(key, options).query(self)
+    }
+
+    /// Modifies an existing redis time series configuration.
+    fn ts_alter<K: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        options: TsOptions,
+    ) -> RedisResult<RV> {
+        commands::ts_alter(key, options).query(self)
+    }
+
+    /// Adds a single time series value with a timestamp to an existing redis time series.
+    fn ts_add<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+    ) -> RedisResult<RV> {
+        commands::ts_add(key, ts, value).query(self)
+    }
+
+    /// Adds a single time series value to an existing redis time series with redis system
+    /// time as timestamp.
+    fn ts_add_now<K: ToRedisArgs, V: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> RedisResult<RV> {
+        commands::ts_add_now(key, value).query(self)
+    }
+
+    /// Adds a single time series value to a redis time series. If the time series does not
+    /// yet exist it will be created with given settings.
+    fn ts_add_create<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+        options: TsOptions,
+    ) -> RedisResult<RV> {
+        commands::ts_add_create(key, ts, value, options).query(self)
+    }
+
+    /// Adds multiple time series values to an existing redis time series.
+    fn ts_madd<K: ToRedisArgs, TS: ToRedisArgs, V: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        values: &[(K, TS, V)],
+    ) -> RedisResult<RV> {
+        commands::ts_madd(values).query(self)
+    }
+
+    /// Increments a time series value with redis system time.
+    fn ts_incrby_now<K: ToRedisArgs, V: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> RedisResult<RV> {
+        commands::ts_incrby_now(key, value).query(self)
+    }
+
+    /// Increments a time series value with given timestamp.
+    fn ts_incrby<K: ToRedisArgs, V: ToRedisArgs, TS: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+    ) -> RedisResult<RV> {
+        commands::ts_incrby(key, ts, value).query(self)
+    }
+
+    /// Increments a time series value with timestamp. Time series will be created if it
+    /// not already exists.
+    fn ts_incrby_create<K: ToRedisArgs, V: ToRedisArgs, TS: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+        options: TsOptions,
+    ) -> RedisResult<RV> {
+        commands::ts_incrby_create(key, ts, value, options).query(self)
+    }
+
+    /// Decrements a time series value with redis system time.
+    fn ts_decrby_now<K: ToRedisArgs, V: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> RedisResult<RV> {
+        commands::ts_decrby_now(key, value).query(self)
+    }
+
+    /// Decrements a time series value with given timestamp.
+    fn ts_decrby<K: ToRedisArgs, V: ToRedisArgs, TS: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+    ) -> RedisResult<RV> {
+        commands::ts_decrby(key, ts, value).query(self)
+    }
+
+    /// Decrements a time series value with timestamp. Time series will be created if it
+    /// not already exists.
+    fn ts_decrby_create<K: ToRedisArgs, V: ToRedisArgs, TS: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        ts: TS,
+        value: V,
+        options: TsOptions,
+    ) -> RedisResult<RV> {
+        commands::ts_decrby_create(key, ts, value, options).query(self)
+    }
+
+    /// Creates a new redis time series compaction rule.
+    fn ts_createrule<K: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        source_key: K,
+        dest_key: K,
+        aggregation_type: TsAggregationType,
+    ) -> RedisResult<RV> {
+        commands::ts_createrule(source_key, dest_key, aggregation_type).query(self)
+    }
+
+    /// Deletes an existing redis time series compaction rule.
+    fn ts_deleterule<K: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        source_key: K,
+        dest_key: K,
+    ) -> RedisResult<RV> {
+        commands::ts_deleterule(source_key, dest_key).query(self)
+    }
+
+    /// Deletes all samples between `from` and `to` (inclusive) from a redis
+    /// time series, returning the number of samples deleted.
+    fn ts_del<K: ToRedisArgs, TS: ToRedisArgs, RV: FromRedisValue>(
+        &mut self,
+        key: K,
+        from: TS,
+        to: TS,
+    ) -> RedisResult<RV> {
+        commands::ts_del(key, from, to).query(self)
+    }
+
+    /// Returns the latest (current) value in a redis time series.
+    fn ts_get<K: ToRedisArgs, TS: FromRedisValue, V: FromRedisValue>(
+        &mut self,
+        key: K,
+    ) -> RedisResult<Option<(TS, V)>> {
+        commands::ts_get(key).query(self).or(Ok(None))
+    }
+
+    /// Returns the latest (current) value from multiple redis time series.
+    fn ts_mget<TS: Default + FromRedisValue, V: Default + FromRedisValue>(
+        &mut self,
+        filter_options: TsFilterOptions,
+    ) -> RedisResult<TsMget<TS, V>> {
+        commands::ts_mget(filter_options).query(self)
+    }
+
+    #[doc(hidden)]
+    fn range<K: ToRedisArgs, TS: Default + FromRedisValue + Copy, V: Default + FromRedisValue + Copy>(
+        &mut self,
+        command: &str,
+        key: K,
+        query: TsRangeQuery,
+    ) -> RedisResult<TsRange<TS, V>> {
+        commands::ts_range(command, key, query).query(self)
+    }
+
+    /// Executes a redis time series range query.
+    fn ts_range<K: ToRedisArgs, TS: Default + FromRedisValue + Copy, V: Default + FromRedisValue + Copy>(
+        &mut self,
+        key: K,
+        query: TsRangeQuery,
+    ) -> RedisResult<TsRange<TS, V>> {
+        self.range("TS.RANGE", key, query)
+    }
+
+    /// Executes a redis time series revrange query.
+    fn ts_revrange<K: ToRedisArgs, TS: Default + FromRedisValue + Copy, V: Default + FromRedisValue + Copy>(
+        &mut self,
+        key: K,
+        query: TsRangeQuery,
+    ) -> RedisResult<TsRange<TS, V>> {
+        self.range("TS.REVRANGE", key, query)
+    }
+
+    /// Executes a redis time series range query, paging through the result
+    /// `chunk_size` samples at a time instead of materializing the whole
+    /// range in memory. See [`TsRangeChunkedIter`].
+    fn ts_range_chunked<
+        'a,
+        K: ToRedisArgs + Clone,
+        TS: Default + FromRedisValue + Copy + PartialEq + Display,
+        V: Default + FromRedisValue + Copy,
+    >(
+        &'a mut self,
+        key: K,
+        query: TsRangeQuery,
+        chunk_size: u64,
+    ) -> TsRangeChunkedIter<'a, Self, K, TS, V> {
+        TsRangeChunkedIter::new(self, "TS.RANGE", key, query, chunk_size)
+    }
+
+    #[doc(hidden)]
+    fn mrange<TS: Default + FromRedisValue + Copy, V: Default + FromRedisValue + Copy>(
+        &mut self,
+        command: &str,
+        query: TsRangeQuery,
+        filter_options: TsFilterOptions,
+    ) -> RedisResult<TsMrange<TS, V>> {
+        let grouped = filter_options.is_grouped();
+        let result: TsMrange<TS, V> = commands::ts_mrange(command, query, filter_options).query(self)?;
+        Ok(if grouped { result.populate_groups() } else { result })
+    }
+
+    /// Executes multiple redis time series range queries.
+    fn ts_mrange<TS: Default + FromRedisValue + Copy, V: Default + FromRedisValue + Copy>(
+        &mut self,
+        query: TsRangeQuery,
+        filter_options: TsFilterOptions,
+    ) -> RedisResult<TsMrange<TS, V>> {
+        self.mrange("TS.MRANGE", query, filter_options)
+    }
+
+    /// Executes multiple redis time series revrange queries.
+    fn ts_mrevrange<TS: Default + FromRedisValue + Copy, V: Default + FromRedisValue + Copy>(
+        &mut self,
+        query: TsRangeQuery,
+        filter_options: TsFilterOptions,
+    ) -> RedisResult<TsMrange<TS, V>> {
+        self.mrange("TS.MREVRANGE", query, filter_options)
+    }
+
+    /// Returns a filtered list of redis time series keys.
+    fn ts_queryindex(&mut self, filter_options: TsFilterOptions) -> RedisResult<Vec<String>> {
+        commands::ts_queryindex(filter_options).query(self)
+    }
+}
+
+impl<T> TsCommands for T where T: ConnectionLike {}
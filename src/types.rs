@@ -0,0 +1,770 @@
+use redis::{ErrorKind, FromRedisValue, RedisError, RedisResult, RedisWrite, ToRedisArgs, Value};
+use std::collections::BTreeMap;
+
+macro_rules! fail {
+    ($expr:expr) => {
+        return Err($expr)
+    };
+}
+
+fn parse_error() -> RedisError {
+    RedisError::from((
+        ErrorKind::TypeError,
+        "Response was of incompatible type",
+        "Could not convert from the redis time series response.".to_string(),
+    ))
+}
+
+/// Policy that determines what happens when a duplicate timestamp is added
+/// to a time series (`TS.ADD`/`TS.CREATE` `DUPLICATE_POLICY`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TsDuplicatePolicy {
+    Block,
+    First,
+    Last,
+    Min,
+    Max,
+    Sum,
+}
+
+impl ToRedisArgs for TsDuplicatePolicy {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let s = match self {
+            TsDuplicatePolicy::Block => "BLOCK",
+            TsDuplicatePolicy::First => "FIRST",
+            TsDuplicatePolicy::Last => "LAST",
+            TsDuplicatePolicy::Min => "MIN",
+            TsDuplicatePolicy::Max => "MAX",
+            TsDuplicatePolicy::Sum => "SUM",
+        };
+        out.write_arg(s.as_bytes());
+    }
+}
+
+/// Options used by `TS.CREATE`, `TS.ALTER`, `TS.ADD` and the `_create`
+/// variants of `TS.INCRBY`/`TS.DECRBY` to configure a time series.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TsOptions {
+    retention_time: Option<u64>,
+    uncompressed: bool,
+    chunk_size: Option<u64>,
+    duplicate_policy: Option<TsDuplicatePolicy>,
+    labels: Vec<(String, String)>,
+}
+
+impl TsOptions {
+    /// Sets the `RETENTION` in milliseconds.
+    pub fn retention_time(mut self, retention_time: u64) -> Self {
+        self.retention_time = Some(retention_time);
+        self
+    }
+
+    /// Controls whether the series is stored `UNCOMPRESSED` (`true`) or
+    /// compressed (`false`, the default).
+    pub fn uncompressed(mut self, uncompressed: bool) -> Self {
+        self.uncompressed = uncompressed;
+        self
+    }
+
+    /// Sets the `CHUNK_SIZE` in bytes.
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Sets the `DUPLICATE_POLICY`.
+    pub fn duplicate_policy(mut self, duplicate_policy: TsDuplicatePolicy) -> Self {
+        self.duplicate_policy = Some(duplicate_policy);
+        self
+    }
+
+    /// Attaches a `LABELS key value` pair.
+    pub fn label<K: ToString, V: ToString>(mut self, key: K, value: V) -> Self {
+        self.labels.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Replaces the full set of `LABELS`.
+    pub fn labels(mut self, labels: Vec<(String, String)>) -> Self {
+        self.labels = labels;
+        self
+    }
+}
+
+impl ToRedisArgs for TsOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if let Some(retention_time) = self.retention_time {
+            out.write_arg(b"RETENTION");
+            retention_time.write_redis_args(out);
+        }
+
+        if self.uncompressed {
+            out.write_arg(b"UNCOMPRESSED");
+        }
+
+        if let Some(chunk_size) = self.chunk_size {
+            out.write_arg(b"CHUNK_SIZE");
+            chunk_size.write_redis_args(out);
+        }
+
+        if let Some(duplicate_policy) = self.duplicate_policy {
+            out.write_arg(b"DUPLICATE_POLICY");
+            duplicate_policy.write_redis_args(out);
+        }
+
+        if !self.labels.is_empty() {
+            out.write_arg(b"LABELS");
+            for (key, value) in &self.labels {
+                out.write_arg(key.as_bytes());
+                out.write_arg(value.as_bytes());
+            }
+        }
+    }
+}
+
+/// The aggregation applied by `TS.CREATERULE` and by range queries
+/// (`AGGREGATION <type> <time_bucket>`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TsAggregationType {
+    Avg(u64),
+    Sum(u64),
+    Min(u64),
+    Max(u64),
+    Range(u64),
+    Count(u64),
+    First(u64),
+    Last(u64),
+    StdP(u64),
+    StdS(u64),
+    VarP(u64),
+    VarS(u64),
+    Twa(u64),
+}
+
+impl ToRedisArgs for TsAggregationType {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let (name, bucket) = match self {
+            TsAggregationType::Avg(b) => ("avg", b),
+            TsAggregationType::Sum(b) => ("sum", b),
+            TsAggregationType::Min(b) => ("min", b),
+            TsAggregationType::Max(b) => ("max", b),
+            TsAggregationType::Range(b) => ("range", b),
+            TsAggregationType::Count(b) => ("count", b),
+            TsAggregationType::First(b) => ("first", b),
+            TsAggregationType::Last(b) => ("last", b),
+            TsAggregationType::StdP(b) => ("std.p", b),
+            TsAggregationType::StdS(b) => ("std.s", b),
+            TsAggregationType::VarP(b) => ("var.p", b),
+            TsAggregationType::VarS(b) => ("var.s", b),
+            TsAggregationType::Twa(b) => ("twa", b),
+        };
+        out.write_arg(b"AGGREGATION");
+        out.write_arg(name.as_bytes());
+        bucket.write_redis_args(out);
+    }
+}
+
+/// The `ALIGN` value of a range query's `AGGREGATION` clause.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TsAlign {
+    Start,
+    End,
+    Value(i64),
+}
+
+impl ToRedisArgs for TsAlign {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        match self {
+            TsAlign::Start => out.write_arg(b"-"),
+            TsAlign::End => out.write_arg(b"+"),
+            TsAlign::Value(v) => v.write_redis_args(out),
+        }
+    }
+}
+
+/// The `BUCKETTIMESTAMP` value of a range query's `AGGREGATION` clause.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TsBucketTimestamp {
+    Start,
+    End,
+    Mid,
+}
+
+impl ToRedisArgs for TsBucketTimestamp {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let s = match self {
+            TsBucketTimestamp::Start => "start",
+            TsBucketTimestamp::End => "end",
+            TsBucketTimestamp::Mid => "mid",
+        };
+        out.write_arg(s.as_bytes());
+    }
+}
+
+/// Options for `TS.RANGE`/`TS.REVRANGE`/`TS.MRANGE`/`TS.MREVRANGE`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TsRangeQuery {
+    from: String,
+    to: String,
+    latest: bool,
+    filter_by_ts: Vec<String>,
+    filter_by_value: Option<(String, String)>,
+    count: Option<u64>,
+    aggregation: Option<TsAggregationType>,
+    align: Option<TsAlign>,
+    bucket_timestamp: Option<TsBucketTimestamp>,
+    empty: bool,
+}
+
+impl TsRangeQuery {
+    /// Creates a new range query covering `[from, to]`. Use `"-"`/`"+"` for
+    /// the unbounded ends, matching the server's own grammar.
+    pub fn new<F: ToString, T: ToString>(from: F, to: T) -> Self {
+        TsRangeQuery {
+            from: from.to_string(),
+            to: to.to_string(),
+            latest: false,
+            filter_by_ts: Vec::new(),
+            filter_by_value: None,
+            count: None,
+            aggregation: None,
+            align: None,
+            bucket_timestamp: None,
+            empty: false,
+        }
+    }
+
+    /// Requests compacted series to also include the latest, possibly
+    /// partial, bucket (`LATEST`).
+    pub fn latest(mut self) -> Self {
+        self.latest = true;
+        self
+    }
+
+    /// Restricts the returned samples to the given timestamps
+    /// (`FILTER_BY_TS ts...`).
+    pub fn filter_by_ts<TS: ToString>(mut self, timestamps: Vec<TS>) -> Self {
+        self.filter_by_ts = timestamps.into_iter().map(|ts| ts.to_string()).collect();
+        self
+    }
+
+    /// Restricts the returned samples to the given value range
+    /// (`FILTER_BY_VALUE min max`).
+    pub fn filter_by_value<V: ToString>(mut self, min: V, max: V) -> Self {
+        self.filter_by_value = Some((min.to_string(), max.to_string()));
+        self
+    }
+
+    /// Limits the number of returned samples via `COUNT`.
+    pub fn count(mut self, count: u64) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Applies an `AGGREGATION` clause.
+    pub fn aggregation_type(mut self, aggregation: TsAggregationType) -> Self {
+        self.aggregation = Some(aggregation);
+        self
+    }
+
+    /// Sets the `ALIGN` value of the `AGGREGATION` clause.
+    pub fn align(mut self, align: TsAlign) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    /// Sets the `BUCKETTIMESTAMP` value of the `AGGREGATION` clause.
+    pub fn bucket_timestamp(mut self, bucket_timestamp: TsBucketTimestamp) -> Self {
+        self.bucket_timestamp = Some(bucket_timestamp);
+        self
+    }
+
+    /// Requests that empty aggregation buckets are still emitted (`EMPTY`).
+    pub fn empty(mut self) -> Self {
+        self.empty = true;
+        self
+    }
+
+    /// Clones this query for a single page of a chunked range read,
+    /// overriding `from` (when paging past the first page) and `COUNT`
+    /// while keeping every other option - aggregation, filters, alignment -
+    /// as originally configured.
+    #[doc(hidden)]
+    pub(crate) fn paged(&self, from: Option<&str>, count: u64) -> Self {
+        let mut query = self.clone();
+        if let Some(from) = from {
+            query.from = from.to_string();
+        }
+        query.count = Some(count);
+        query
+    }
+}
+
+impl TsRangeQuery {
+    /// Writes `fromTimestamp toTimestamp [LATEST] [FILTER_BY_TS ...]
+    /// [FILTER_BY_VALUE ...]` - everything the grammar places *before* the
+    /// optional `WITHLABELS`/`SELECTED_LABELS` clause of
+    /// `TS.MRANGE`/`TS.MREVRANGE`. Used by [`crate::commands::ts_mrange`] to
+    /// interleave with [`TsFilterOptions`]; [`Self::write_redis_args`] calls
+    /// this too for the single-key commands, where there is no such clause
+    /// to interleave with.
+    #[doc(hidden)]
+    pub(crate) fn write_range_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.from.as_bytes());
+        out.write_arg(self.to.as_bytes());
+
+        if self.latest {
+            out.write_arg(b"LATEST");
+        }
+
+        if !self.filter_by_ts.is_empty() {
+            out.write_arg(b"FILTER_BY_TS");
+            for ts in &self.filter_by_ts {
+                out.write_arg(ts.as_bytes());
+            }
+        }
+
+        if let Some((min, max)) = &self.filter_by_value {
+            out.write_arg(b"FILTER_BY_VALUE");
+            out.write_arg(min.as_bytes());
+            out.write_arg(max.as_bytes());
+        }
+    }
+
+    /// Writes `[COUNT ...] [[ALIGN ...] AGGREGATION ...]` - the grammar
+    /// placed *after* the optional `WITHLABELS`/`SELECTED_LABELS` clause of
+    /// `TS.MRANGE`/`TS.MREVRANGE`. See [`Self::write_range_args`].
+    #[doc(hidden)]
+    pub(crate) fn write_aggregation_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if let Some(count) = self.count {
+            out.write_arg(b"COUNT");
+            count.write_redis_args(out);
+        }
+
+        if let Some(aggregation) = self.aggregation {
+            if let Some(align) = &self.align {
+                out.write_arg(b"ALIGN");
+                align.write_redis_args(out);
+            }
+
+            aggregation.write_redis_args(out);
+
+            if let Some(bucket_timestamp) = self.bucket_timestamp {
+                out.write_arg(b"BUCKETTIMESTAMP");
+                bucket_timestamp.write_redis_args(out);
+            }
+
+            if self.empty {
+                out.write_arg(b"EMPTY");
+            }
+        }
+    }
+}
+
+impl ToRedisArgs for TsRangeQuery {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        self.write_range_args(out);
+        self.write_aggregation_args(out);
+    }
+}
+
+/// The `REDUCE` function of a `GROUPBY` clause.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TsReducer {
+    Avg,
+    Sum,
+    Min,
+    Max,
+    Range,
+    Count,
+    StdP,
+    StdS,
+    VarP,
+    VarS,
+}
+
+impl ToRedisArgs for TsReducer {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let s = match self {
+            TsReducer::Avg => "avg",
+            TsReducer::Sum => "sum",
+            TsReducer::Min => "min",
+            TsReducer::Max => "max",
+            TsReducer::Range => "range",
+            TsReducer::Count => "count",
+            TsReducer::StdP => "std.p",
+            TsReducer::StdS => "std.s",
+            TsReducer::VarP => "var.p",
+            TsReducer::VarS => "var.s",
+        };
+        out.write_arg(s.as_bytes());
+    }
+}
+
+/// A `GROUPBY <label> REDUCE <reducer>` clause, collapsing the series
+/// matched by a `TS.MRANGE`/`TS.MREVRANGE` filter into one aggregate per
+/// distinct value of `label`. `TS.MGET` drops this clause.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TsGroupBy {
+    pub label: String,
+    pub reducer: TsReducer,
+}
+
+impl TsGroupBy {
+    pub fn new<L: ToString>(label: L, reducer: TsReducer) -> Self {
+        TsGroupBy {
+            label: label.to_string(),
+            reducer,
+        }
+    }
+}
+
+impl ToRedisArgs for TsGroupBy {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(b"GROUPBY");
+        out.write_arg(self.label.as_bytes());
+        out.write_arg(b"REDUCE");
+        self.reducer.write_redis_args(out);
+    }
+}
+
+/// Filter used by `TS.MGET`/`TS.MRANGE`/`TS.MREVRANGE`/`TS.QUERYINDEX`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TsFilterOptions {
+    filters: Vec<String>,
+    with_labels: bool,
+    selected_labels: Vec<String>,
+    group_by: Option<TsGroupBy>,
+}
+
+impl TsFilterOptions {
+    /// Creates a new filter from the given `label=value`/`label!=`
+    /// expressions. At least one is required by the server.
+    pub fn new(filters: Vec<String>) -> Self {
+        TsFilterOptions {
+            filters,
+            with_labels: false,
+            selected_labels: Vec::new(),
+            group_by: None,
+        }
+    }
+
+    /// Requests that matched series' labels are returned (`WITHLABELS`).
+    pub fn with_labels(mut self, with_labels: bool) -> Self {
+        self.with_labels = with_labels;
+        self
+    }
+
+    /// Requests only the given labels are returned (`SELECTED_LABELS`),
+    /// instead of the full label set `WITHLABELS` would return.
+    pub fn selected_labels(mut self, labels: Vec<String>) -> Self {
+        self.selected_labels = labels;
+        self
+    }
+
+    /// Collapses the matched series with a `GROUPBY`/`REDUCE` clause.
+    pub fn group_by(mut self, group_by: TsGroupBy) -> Self {
+        self.group_by = Some(group_by);
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn get_filters(&self) -> Vec<String> {
+        self.filters.clone()
+    }
+
+    /// Whether a `GROUPBY`/`REDUCE` clause was requested.
+    #[doc(hidden)]
+    pub(crate) fn is_grouped(&self) -> bool {
+        self.group_by.is_some()
+    }
+
+    /// `TS.MGET` has no `GROUPBY` clause - unlike `TS.MRANGE`/`TS.MREVRANGE`,
+    /// which share this same filter type, it would reject a command that
+    /// carried one. Used by [`crate::commands::ts_mget`] to strip it before
+    /// serializing.
+    #[doc(hidden)]
+    pub(crate) fn without_group_by(mut self) -> Self {
+        self.group_by = None;
+        self
+    }
+}
+
+impl TsFilterOptions {
+    /// Writes `[WITHLABELS | SELECTED_LABELS label...]` - the grammar places
+    /// this clause *before* `COUNT`/`AGGREGATION` on `TS.MRANGE`/
+    /// `TS.MREVRANGE`, so [`crate::commands::ts_mrange`] interleaves this
+    /// with [`TsRangeQuery::write_range_args`]/
+    /// [`TsRangeQuery::write_aggregation_args`] instead of going through
+    /// [`Self::write_redis_args`].
+    #[doc(hidden)]
+    pub(crate) fn write_label_selection_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if !self.selected_labels.is_empty() {
+            out.write_arg(b"SELECTED_LABELS");
+            for label in &self.selected_labels {
+                out.write_arg(label.as_bytes());
+            }
+        } else if self.with_labels {
+            out.write_arg(b"WITHLABELS");
+        }
+    }
+
+    /// Writes `FILTER filter... [GROUPBY label REDUCE reducer]` - the
+    /// trailing clause shared by `TS.MGET`/`TS.MRANGE`/`TS.MREVRANGE`. See
+    /// [`Self::write_label_selection_args`].
+    #[doc(hidden)]
+    pub(crate) fn write_filter_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(b"FILTER");
+        for filter in &self.filters {
+            out.write_arg(filter.as_bytes());
+        }
+
+        if let Some(group_by) = &self.group_by {
+            group_by.write_redis_args(out);
+        }
+    }
+}
+
+impl ToRedisArgs for TsFilterOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        self.write_label_selection_args(out);
+        self.write_filter_args(out);
+    }
+}
+
+/// Information about a time series key, as returned by `TS.INFO`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TsInfo {
+    pub total_samples: u64,
+    pub memory_usage: u64,
+    pub first_timestamp: u64,
+    pub last_timestamp: u64,
+    pub retention_time: u64,
+    pub chunk_count: u64,
+    pub duplicate_policy: Option<String>,
+    pub labels: Vec<(String, String)>,
+    pub source_key: Option<String>,
+    pub rules: Vec<(String, u64, String)>,
+}
+
+impl FromRedisValue for TsInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let map: BTreeMap<String, Value> = BTreeMap::from_redis_value(v)?;
+        let mut info = TsInfo::default();
+
+        for (key, value) in map {
+            match key.as_str() {
+                "totalSamples" => info.total_samples = u64::from_redis_value(&value)?,
+                "memoryUsage" => info.memory_usage = u64::from_redis_value(&value)?,
+                "firstTimestamp" => info.first_timestamp = u64::from_redis_value(&value)?,
+                "lastTimestamp" => info.last_timestamp = u64::from_redis_value(&value)?,
+                "retentionTime" => info.retention_time = u64::from_redis_value(&value)?,
+                "chunkCount" => info.chunk_count = u64::from_redis_value(&value)?,
+                "duplicatePolicy" => {
+                    info.duplicate_policy = Option::<String>::from_redis_value(&value)?
+                }
+                "sourceKey" => info.source_key = Option::<String>::from_redis_value(&value)?,
+                "labels" => info.labels = parse_label_pairs(&value)?,
+                "rules" => info.rules = parse_rules(&value)?,
+                _ => {}
+            }
+        }
+
+        Ok(info)
+    }
+}
+
+fn parse_label_pairs(v: &Value) -> RedisResult<Vec<(String, String)>> {
+    let raw: Vec<Vec<String>> = Vec::from_redis_value(v)?;
+    Ok(raw
+        .into_iter()
+        .filter_map(|mut pair| {
+            if pair.len() == 2 {
+                let value = pair.remove(1);
+                let key = pair.remove(0);
+                Some((key, value))
+            } else {
+                None
+            }
+        })
+        .collect())
+}
+
+fn parse_rules(v: &Value) -> RedisResult<Vec<(String, u64, String)>> {
+    if let Value::Bulk(items) = v {
+        items
+            .iter()
+            .map(|item| {
+                let rule: Vec<Value> = Vec::from_redis_value(item)?;
+                if rule.len() != 3 {
+                    fail!(parse_error());
+                }
+                let dest_key = String::from_redis_value(&rule[0])?;
+                let bucket = u64::from_redis_value(&rule[1])?;
+                let aggregation = String::from_redis_value(&rule[2])?;
+                Ok((dest_key, bucket, aggregation))
+            })
+            .collect()
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// The result of a `TS.RANGE`/`TS.REVRANGE` call.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TsRange<TS, V> {
+    pub values: Vec<(TS, V)>,
+}
+
+impl<TS: FromRedisValue, V: FromRedisValue> FromRedisValue for TsRange<TS, V> {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let values: Vec<(TS, V)> = Vec::from_redis_value(v)?;
+        Ok(TsRange { values })
+    }
+}
+
+/// One series' worth of labels plus its current sample, as returned by
+/// `TS.MGET`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TsMgetEntry<TS, V> {
+    pub key: String,
+    pub labels: Vec<(String, String)>,
+    pub value: Option<(TS, V)>,
+}
+
+/// The result of a `TS.MGET` call.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TsMget<TS, V> {
+    pub values: Vec<TsMgetEntry<TS, V>>,
+}
+
+impl<TS: Default + FromRedisValue, V: Default + FromRedisValue> FromRedisValue for TsMget<TS, V> {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        if let Value::Bulk(items) = v {
+            let values = items
+                .iter()
+                .map(|item| {
+                    let row: Vec<Value> = Vec::from_redis_value(item)?;
+                    if row.len() != 3 {
+                        fail!(parse_error());
+                    }
+                    let key = String::from_redis_value(&row[0])?;
+                    let labels = parse_label_pairs(&row[1])?;
+                    let value = Option::<(TS, V)>::from_redis_value(&row[2])?;
+                    Ok(TsMgetEntry { key, labels, value })
+                })
+                .collect::<RedisResult<Vec<_>>>()?;
+            Ok(TsMget { values })
+        } else {
+            fail!(parse_error())
+        }
+    }
+}
+
+/// One series' worth of labels plus its ranged samples, as returned by
+/// `TS.MRANGE`/`TS.MREVRANGE`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TsMrangeEntry<TS, V> {
+    pub key: String,
+    pub labels: Vec<(String, String)>,
+    pub values: Vec<(TS, V)>,
+    /// The `(label, value)` this entry was grouped by, when the query used
+    /// a `GROUPBY`/`REDUCE` clause. Redis reports the group as a
+    /// `label=value` key, which is split out here for convenience.
+    pub group: Option<(String, String)>,
+}
+
+/// The result of a `TS.MRANGE`/`TS.MREVRANGE` call.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TsMrange<TS, V> {
+    pub values: Vec<TsMrangeEntry<TS, V>>,
+}
+
+impl<TS, V> TsMrange<TS, V> {
+    /// Splits each entry's `label=value` key into `group`, for a query that
+    /// actually used a `GROUPBY` clause. An ordinary series key is never
+    /// reinterpreted this way, since an ungrouped key happening to contain
+    /// `=` is not a group marker.
+    #[doc(hidden)]
+    pub(crate) fn populate_groups(mut self) -> Self {
+        for entry in &mut self.values {
+            entry.group = entry
+                .key
+                .split_once('=')
+                .map(|(label, value)| (label.to_string(), value.to_string()));
+        }
+        self
+    }
+}
+
+impl<TS: Default + FromRedisValue, V: Default + FromRedisValue> FromRedisValue
+    for TsMrange<TS, V>
+{
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        if let Value::Bulk(items) = v {
+            let values = items
+                .iter()
+                .map(|item| {
+                    let row: Vec<Value> = Vec::from_redis_value(item)?;
+                    if row.len() != 3 {
+                        fail!(parse_error());
+                    }
+                    let key = String::from_redis_value(&row[0])?;
+                    let labels = parse_label_pairs(&row[1])?;
+                    let values: Vec<(TS, V)> = Vec::from_redis_value(&row[2])?;
+                    // `group` is only meaningful when the query carried a
+                    // GROUPBY clause - see `TsMrange::populate_groups`, which
+                    // the caller runs once it knows whether that was the case.
+                    Ok(TsMrangeEntry {
+                        key,
+                        labels,
+                        values,
+                        group: None,
+                    })
+                })
+                .collect::<RedisResult<Vec<_>>>()?;
+            Ok(TsMrange { values })
+        } else {
+            fail!(parse_error())
+        }
+    }
+}